@@ -0,0 +1,292 @@
+use crate::cpu::PROGRAM_START;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// Decodes a stream of CHIP-8 opcodes into `(address, mnemonic)` pairs, one
+/// per 2-byte instruction. Addresses start at `PROGRAM_START` since that's
+/// where a loaded ROM is placed in RAM. Unrecognized opcodes are rendered as
+/// a raw `DATA` directive instead of being skipped.
+pub fn disassemble(bytes: &[u8]) -> Vec<(u16, String)> {
+    let mut out = Vec::new();
+
+    for (i, chunk) in bytes.chunks(2).enumerate() {
+        let address = PROGRAM_START + (i as u16) * 2;
+        if chunk.len() < 2 {
+            out.push((address, format!("DATA {:#04X}", chunk[0])));
+            break;
+        }
+
+        let opcode = ((chunk[0] as u16) << 8) | chunk[1] as u16;
+        out.push((address, disassemble_one(opcode)));
+    }
+
+    out
+}
+
+fn disassemble_one(opcode: u16) -> String {
+    let nnn = opcode & 0x0FFF;
+    let nn = (opcode & 0x00FF) as u8;
+    let n = (opcode & 0x000F) as u8;
+    let x = ((opcode & 0x0F00) >> 8) as u8;
+    let y = ((opcode & 0x00F0) >> 4) as u8;
+
+    match (opcode & 0xF000) >> 12 {
+        0x0 => match nn {
+            0xE0 => "CLS".to_string(),
+            0xEE => "RET".to_string(),
+            _ => data(opcode),
+        },
+        0x1 => format!("JP {:#05X}", nnn),
+        0x2 => format!("CALL {:#05X}", nnn),
+        0x3 => format!("SE V{:X}, {:#04X}", x, nn),
+        0x4 => format!("SNE V{:X}, {:#04X}", x, nn),
+        0x5 if n == 0 => format!("SE V{:X}, V{:X}", x, y),
+        0x6 => format!("LD V{:X}, {:#04X}", x, nn),
+        0x7 => format!("ADD V{:X}, {:#04X}", x, nn),
+        0x8 => match n {
+            0x0 => format!("LD V{:X}, V{:X}", x, y),
+            0x1 => format!("OR V{:X}, V{:X}", x, y),
+            0x2 => format!("AND V{:X}, V{:X}", x, y),
+            0x3 => format!("XOR V{:X}, V{:X}", x, y),
+            0x4 => format!("ADD V{:X}, V{:X}", x, y),
+            0x5 => format!("SUB V{:X}, V{:X}", x, y),
+            0x6 => format!("SHR V{:X}, V{:X}", x, y),
+            0x7 => format!("SUBN V{:X}, V{:X}", x, y),
+            0xE => format!("SHL V{:X}, V{:X}", x, y),
+            _ => data(opcode),
+        },
+        0x9 if n == 0 => format!("SNE V{:X}, V{:X}", x, y),
+        0xA => format!("LD I, {:#05X}", nnn),
+        0xB => format!("JP V0, {:#05X}", nnn),
+        0xC => format!("RND V{:X}, {:#04X}", x, nn),
+        0xD => format!("DRW V{:X}, V{:X}, {:#03X}", x, y, n),
+        0xE => match nn {
+            0x9E => format!("SKP V{:X}", x),
+            0xA1 => format!("SKNP V{:X}", x),
+            _ => data(opcode),
+        },
+        0xF => match nn {
+            0x07 => format!("LD V{:X}, DT", x),
+            0x0A => format!("LD V{:X}, K", x),
+            0x15 => format!("LD DT, V{:X}", x),
+            0x18 => format!("LD ST, V{:X}", x),
+            0x1E => format!("ADD I, V{:X}", x),
+            0x29 => format!("LD F, V{:X}", x),
+            0x33 => format!("LD B, V{:X}", x),
+            0x55 => format!("LD [I], V{:X}", x),
+            0x65 => format!("LD V{:X}, [I]", x),
+            _ => data(opcode),
+        },
+        _ => data(opcode),
+    }
+}
+
+fn data(opcode: u16) -> String {
+    format!("DATA {:#06X}", opcode)
+}
+
+/// Assembles the mnemonics produced by `disassemble` (plus `label:` defs)
+/// back into a byte stream loadable via `Chip8::load`. Labels may be
+/// referenced before they're defined; they're resolved in a second pass.
+pub fn assemble(source: &str) -> Result<Vec<u8>> {
+    let lines: Vec<&str> = source
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut labels = HashMap::new();
+    let mut address = PROGRAM_START;
+    let mut instructions = Vec::new();
+
+    for line in &lines {
+        if let Some(label) = line.strip_suffix(':') {
+            labels.insert(label.to_uppercase(), address);
+        } else {
+            instructions.push((address, *line));
+            address += 2;
+        }
+    }
+
+    let mut out = Vec::with_capacity(instructions.len() * 2);
+    for (address, line) in instructions {
+        let opcode = assemble_one(line, address, &labels)?;
+        out.push((opcode >> 8) as u8);
+        out.push((opcode & 0xFF) as u8);
+    }
+
+    Ok(out)
+}
+
+fn assemble_one(line: &str, address: u16, labels: &HashMap<String, u16>) -> Result<u16> {
+    let mut parts = line.splitn(2, char::is_whitespace);
+    let mnemonic = parts.next().unwrap_or("").to_uppercase();
+    let operands: Vec<String> = parts
+        .next()
+        .unwrap_or("")
+        .split(',')
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let operands: Vec<&str> = operands.iter().map(|s| s.as_str()).collect();
+
+    let reg = |s: &str| -> Result<u8> {
+        let digits = s.trim_start_matches(['V', 'v']);
+        let reg = u8::from_str_radix(digits, 16).map_err(|_| anyhow::anyhow!("bad register `{}`", s))?;
+        if reg > 0xF {
+            bail!("register `{}` out of range (V0-VF)", s);
+        }
+        Ok(reg)
+    };
+    let addr = |s: &str| -> Result<u16> { resolve_address(s, labels) };
+    let imm = |s: &str| -> Result<u8> {
+        let value = parse_number(s)?;
+        u8::try_from(value).map_err(|_| anyhow::anyhow!("immediate `{}` out of range (0x00-0xFF)", s))
+    };
+    let nibble = |s: &str| -> Result<u16> {
+        let value = parse_number(s)?;
+        if value > 0xF {
+            bail!("sprite height `{}` out of range (0x0-0xF)", s);
+        }
+        Ok(value)
+    };
+
+    let opcode = match (mnemonic.as_str(), operands.as_slice()) {
+        ("CLS", []) => 0x00E0,
+        ("RET", []) => 0x00EE,
+        ("JP", [a]) => 0x1000 | addr(a)?,
+        ("JP", [v0, a]) if v0.eq_ignore_ascii_case("v0") => 0xB000 | addr(a)?,
+        ("CALL", [a]) => 0x2000 | addr(a)?,
+        ("SE", [vx, a]) if a.starts_with(['V', 'v']) => {
+            0x5000 | (reg(vx)? as u16) << 8 | (reg(a)? as u16) << 4
+        }
+        ("SE", [vx, nn]) => 0x3000 | (reg(vx)? as u16) << 8 | imm(nn)? as u16,
+        ("SNE", [vx, a]) if a.starts_with(['V', 'v']) => {
+            0x9000 | (reg(vx)? as u16) << 8 | (reg(a)? as u16) << 4
+        }
+        ("SNE", [vx, nn]) => 0x4000 | (reg(vx)? as u16) << 8 | imm(nn)? as u16,
+        ("LD", [vx, "DT"]) => 0xF007 | (reg(vx)? as u16) << 8,
+        ("LD", [vx, "K"]) => 0xF00A | (reg(vx)? as u16) << 8,
+        ("LD", ["DT", vx]) => 0xF015 | (reg(vx)? as u16) << 8,
+        ("LD", ["ST", vx]) => 0xF018 | (reg(vx)? as u16) << 8,
+        ("LD", ["F", vx]) => 0xF029 | (reg(vx)? as u16) << 8,
+        ("LD", ["B", vx]) => 0xF033 | (reg(vx)? as u16) << 8,
+        ("LD", ["[I]", vx]) => 0xF055 | (reg(vx)? as u16) << 8,
+        ("LD", [vx, "[I]"]) => 0xF065 | (reg(vx)? as u16) << 8,
+        ("LD", ["I", a]) => 0xA000 | addr(a)?,
+        ("LD", [vx, vy]) if vy.starts_with(['V', 'v']) => {
+            0x8000 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4
+        }
+        ("LD", [vx, nn]) => 0x6000 | (reg(vx)? as u16) << 8 | imm(nn)? as u16,
+        ("ADD", ["I", vx]) => 0xF01E | (reg(vx)? as u16) << 8,
+        ("ADD", [vx, vy]) if vy.starts_with(['V', 'v']) => {
+            0x8004 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4
+        }
+        ("ADD", [vx, nn]) => 0x7000 | (reg(vx)? as u16) << 8 | imm(nn)? as u16,
+        ("OR", [vx, vy]) => 0x8001 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("AND", [vx, vy]) => 0x8002 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("XOR", [vx, vy]) => 0x8003 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("SUB", [vx, vy]) => 0x8005 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("SHR", [vx, vy]) => 0x8006 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("SUBN", [vx, vy]) => 0x8007 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("SHL", [vx, vy]) => 0x800E | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4,
+        ("RND", [vx, nn]) => 0xC000 | (reg(vx)? as u16) << 8 | imm(nn)? as u16,
+        ("DRW", [vx, vy, n]) => {
+            0xD000 | (reg(vx)? as u16) << 8 | (reg(vy)? as u16) << 4 | nibble(n)?
+        }
+        ("SKP", [vx]) => 0xE09E | (reg(vx)? as u16) << 8,
+        ("SKNP", [vx]) => 0xE0A1 | (reg(vx)? as u16) << 8,
+        ("DATA", [value]) => parse_number(value)?,
+        _ => bail!("unrecognized instruction `{}` at {:#06X}", line, address),
+    };
+
+    Ok(opcode)
+}
+
+fn resolve_address(token: &str, labels: &HashMap<String, u16>) -> Result<u16> {
+    if let Some(&address) = labels.get(token) {
+        return Ok(address);
+    }
+    parse_number(token)
+}
+
+fn parse_number(token: &str) -> Result<u16> {
+    let token = token.trim();
+    if let Some(hex) = token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+        u16::from_str_radix(hex, 16).map_err(|_| anyhow::anyhow!("bad number `{}`", token))
+    } else {
+        token
+            .parse()
+            .map_err(|_| anyhow::anyhow!("bad number `{}`", token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assemble_then_disassemble_round_trips_a_representative_program() {
+        let source = "\
+            LD V0, 0x0A\n\
+            LD V1, V0\n\
+            ADD V0, 0x01\n\
+            SE V0, V1\n\
+            DRW V0, V1, 0x5\n\
+            CLS\n\
+            RET\n\
+        ";
+
+        let bytes = assemble(source).unwrap();
+        let mnemonics: Vec<String> = disassemble(&bytes).into_iter().map(|(_, m)| m).collect();
+
+        assert_eq!(
+            mnemonics,
+            vec![
+                "LD V0, 0x0A",
+                "LD V1, V0",
+                "ADD V0, 0x01",
+                "SE V0, V1",
+                "DRW V0, V1, 0x5",
+                "CLS",
+                "RET",
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_resolves_a_forward_reference_label() {
+        let source = "\
+            JP LOOP\n\
+            CLS\n\
+            LOOP:\n\
+            RET\n\
+        ";
+
+        let bytes = assemble(source).unwrap();
+
+        // JP LOOP must target the address of the RET instruction, two
+        // instructions after the start of the program.
+        let target = PROGRAM_START + 4;
+        assert_eq!(bytes[0], (0x10 | (target >> 8)) as u8);
+        assert_eq!(bytes[1], (target & 0xFF) as u8);
+    }
+
+    #[test]
+    fn assemble_rejects_an_out_of_range_register() {
+        let result = assemble("SKP V16");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn assemble_rejects_an_out_of_range_immediate() {
+        let result = assemble("ADD V0, 300");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn assemble_rejects_an_out_of_range_sprite_height() {
+        let result = assemble("DRW V0, V1, 0x20");
+        assert!(result.is_err());
+    }
+}
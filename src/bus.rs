@@ -1,15 +1,23 @@
+use crate::display::Display;
+use crate::keypad::Keypad;
 use crate::ram::Ram;
 
 pub struct Bus {
     ram: Ram,
+    display: Display,
+    keypad: Keypad,
     delay_timer: u8,
+    sound_timer: u8,
 }
 
 impl Bus {
     pub fn new() -> Self {
         Self {
             ram: Ram::new(),
+            display: Display::new(),
+            keypad: Keypad::new(),
             delay_timer: 0,
+            sound_timer: 0,
         }
     }
 
@@ -20,4 +28,87 @@ impl Bus {
     pub fn load_byte(&mut self, address: u16, value: u8) {
         self.ram.load_byte(address, value);
     }
+
+    pub fn read_bytes(&self, address: u16, len: u16) -> &[u8] {
+        self.ram.read_bytes(address, len)
+    }
+
+    pub fn try_read_byte(&self, address: u16) -> Option<u8> {
+        self.ram.try_read_byte(address)
+    }
+
+    pub fn try_read_bytes(&self, address: u16, len: u16) -> Option<&[u8]> {
+        self.ram.try_read_bytes(address, len)
+    }
+
+    pub fn try_load_byte(&mut self, address: u16, value: u8) -> Option<()> {
+        self.ram.try_load_byte(address, value)
+    }
+
+    pub fn clear_screen(&mut self) {
+        self.display.clear();
+    }
+
+    pub fn draw_sprite(&mut self, x: u8, y: u8, sprite: &[u8]) -> bool {
+        self.display.draw_sprite(x, y, sprite)
+    }
+
+    pub fn framebuffer(&self) -> &[bool] {
+        self.display.pixels()
+    }
+
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.keypad.set_key(key, pressed);
+    }
+
+    pub fn is_pressed(&self, key: u8) -> bool {
+        self.keypad.is_pressed(key)
+    }
+
+    pub fn pressed_key(&self) -> Option<u8> {
+        self.keypad.pressed_key()
+    }
+
+    pub fn delay_timer(&self) -> u8 {
+        self.delay_timer
+    }
+
+    pub fn set_delay_timer(&mut self, value: u8) {
+        self.delay_timer = value;
+    }
+
+    pub fn sound_timer(&self) -> u8 {
+        self.sound_timer
+    }
+
+    pub fn set_sound_timer(&mut self, value: u8) {
+        self.sound_timer = value;
+    }
+
+    /// Decrements both timers toward zero. Meant to be driven at 60Hz,
+    /// independently of the CPU's instruction clock.
+    pub fn tick_timers(&mut self) {
+        self.delay_timer = self.delay_timer.saturating_sub(1);
+        self.sound_timer = self.sound_timer.saturating_sub(1);
+    }
+
+    pub fn ram_bytes(&self) -> &[u8] {
+        self.ram.as_bytes()
+    }
+
+    pub fn load_ram(&mut self, bytes: &[u8]) {
+        self.ram.load_bytes(bytes);
+    }
+
+    pub fn keys(&self) -> &[bool; crate::keypad::NUM_KEYS] {
+        self.keypad.keys()
+    }
+
+    pub fn restore_keys(&mut self, keys: [bool; crate::keypad::NUM_KEYS]) {
+        self.keypad.restore(keys);
+    }
+
+    pub fn restore_framebuffer(&mut self, pixels: &[bool]) {
+        self.display.restore(pixels);
+    }
 }
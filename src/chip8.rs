@@ -1,11 +1,22 @@
 use crate::bus::Bus;
 use crate::cpu::Cpu;
 use crate::cpu::PROGRAM_START;
-use anyhow::Result;
+use crate::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use crate::error::ExecError;
+use crate::keypad::NUM_KEYS;
+use crate::quirks::Quirks;
+use crate::ram::MEMORY_SIZE;
+use anyhow::{bail, Result};
 use std::fs;
 use std::io::Read;
 use std::path::Path;
 
+/// Identifies a save-state blob produced by `Chip8::save_state`.
+const SNAPSHOT_MAGIC: &[u8; 4] = b"C8SS";
+/// Bumped whenever the snapshot layout changes, so old/new saves are rejected
+/// cleanly instead of being misread.
+const SNAPSHOT_VERSION: u8 = 1;
+
 pub struct Chip8 {
     bus: Bus,
     cpu: Cpu,
@@ -13,9 +24,13 @@ pub struct Chip8 {
 
 impl Chip8 {
     pub fn new() -> Self {
+        Self::with_quirks(Quirks::default())
+    }
+
+    pub fn with_quirks(quirks: Quirks) -> Self {
         Self {
             bus: Bus::new(),
-            cpu: Cpu::new(),
+            cpu: Cpu::new(quirks),
         }
     }
 
@@ -29,13 +44,216 @@ impl Chip8 {
         Ok(())
     }
 
+    /// Loads `buffer` into RAM starting at `PROGRAM_START`. Bytes that would
+    /// fall outside of RAM are silently dropped instead of panicking, so this
+    /// is safe to call with an oversized or otherwise arbitrary buffer.
     pub fn load(&mut self, buffer: Vec<u8>) {
         for (i, byte) in buffer.iter().enumerate() {
-            self.bus.load_byte(PROGRAM_START + (i as u16), byte.clone());
+            let address = PROGRAM_START as usize + i;
+            if address > u16::MAX as usize {
+                break;
+            }
+            if self.bus.try_load_byte(address as u16, *byte).is_none() {
+                break;
+            }
+        }
+    }
+
+    pub fn clock(&mut self) -> Result<(), ExecError> {
+        self.cpu.exec_instruction(&mut self.bus)
+    }
+
+    /// Decrements the delay and sound timers. Should be driven at 60Hz by the
+    /// caller, independently of how often `clock()` is called.
+    pub fn tick_timers(&mut self) {
+        self.bus.tick_timers();
+    }
+
+    pub fn is_sound_playing(&self) -> bool {
+        self.bus.sound_timer() > 0
+    }
+
+    pub fn framebuffer(&self) -> &[bool] {
+        self.bus.framebuffer()
+    }
+
+    pub fn registers(&self) -> &[u8; 16] {
+        self.cpu.registers()
+    }
+
+    pub fn memory(&self) -> &[u8] {
+        self.bus.ram_bytes()
+    }
+
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.bus.set_key(key, pressed);
+    }
+
+    /// Serializes the full machine state (RAM, registers, timers, keypad and
+    /// framebuffer) into a versioned blob suitable for a front-end quicksave.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SNAPSHOT_MAGIC);
+        out.push(SNAPSHOT_VERSION);
+
+        out.extend_from_slice(self.bus.ram_bytes());
+
+        out.extend_from_slice(self.cpu.registers());
+        out.extend_from_slice(&self.cpu.pc().to_le_bytes());
+        out.extend_from_slice(&self.cpu.idx().to_le_bytes());
+
+        let stack = self.cpu.stack();
+        out.push(stack.len() as u8);
+        for frame in stack {
+            out.extend_from_slice(&frame.to_le_bytes());
+        }
+
+        out.push(self.bus.delay_timer());
+        out.push(self.bus.sound_timer());
+
+        for key in self.bus.keys() {
+            out.push(*key as u8);
+        }
+
+        for pixel in self.bus.framebuffer() {
+            out.push(*pixel as u8);
+        }
+
+        out
+    }
+
+    /// Restores state previously produced by `save_state`. Rejects blobs with
+    /// an unrecognized magic or a version this build doesn't know how to read.
+    pub fn load_state(&mut self, data: &[u8]) -> Result<()> {
+        let mut cursor = data;
+
+        let magic = take(&mut cursor, 4)?;
+        if magic != SNAPSHOT_MAGIC {
+            bail!("not a chip8 save state");
+        }
+
+        let version = take(&mut cursor, 1)?[0];
+        if version != SNAPSHOT_VERSION {
+            bail!(
+                "unsupported save state version {} (expected {})",
+                version,
+                SNAPSHOT_VERSION
+            );
+        }
+
+        let ram = take(&mut cursor, MEMORY_SIZE)?;
+        self.bus.load_ram(ram);
+
+        let mut vx = [0u8; 16];
+        vx.copy_from_slice(take(&mut cursor, 16)?);
+        let pc = u16::from_le_bytes(take(&mut cursor, 2)?.try_into().unwrap());
+        let idx = u16::from_le_bytes(take(&mut cursor, 2)?.try_into().unwrap());
+
+        let stack_len = take(&mut cursor, 1)?[0] as usize;
+        let mut stack = Vec::with_capacity(stack_len);
+        for _ in 0..stack_len {
+            stack.push(u16::from_le_bytes(take(&mut cursor, 2)?.try_into().unwrap()));
         }
+        self.cpu.restore(vx, pc, idx, stack);
+
+        self.bus.set_delay_timer(take(&mut cursor, 1)?[0]);
+        self.bus.set_sound_timer(take(&mut cursor, 1)?[0]);
+
+        let mut keys = [false; NUM_KEYS];
+        for (i, byte) in take(&mut cursor, NUM_KEYS)?.iter().enumerate() {
+            keys[i] = *byte != 0;
+        }
+        self.bus.restore_keys(keys);
+
+        let fb_len = DISPLAY_WIDTH * DISPLAY_HEIGHT;
+        let pixels: Vec<bool> = take(&mut cursor, fb_len)?.iter().map(|b| *b != 0).collect();
+        self.bus.restore_framebuffer(&pixels);
+
+        Ok(())
+    }
+
+    pub fn save_state_file<P: AsRef<Path>>(&self, filename: P) -> Result<()> {
+        fs::write(filename, self.save_state())?;
+        Ok(())
+    }
+
+    pub fn load_state_file<P: AsRef<Path>>(&mut self, filename: P) -> Result<()> {
+        let data = fs::read(filename)?;
+        self.load_state(&data)
+    }
+}
+
+/// Splits `len` bytes off the front of `cursor`, advancing it past them.
+fn take<'a>(cursor: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if cursor.len() < len {
+        bail!("save state data is truncated");
+    }
+    let (head, tail) = cursor.split_at(len);
+    *cursor = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_truncates_instead_of_panicking_on_an_oversized_buffer() {
+        let mut chip8 = Chip8::new();
+        let buffer = vec![0xABu8; MEMORY_SIZE];
+
+        chip8.load(buffer);
+
+        let last_in_range = MEMORY_SIZE - PROGRAM_START as usize - 1;
+        assert_eq!(chip8.memory()[PROGRAM_START as usize + last_in_range], 0xAB);
+    }
+
+    #[test]
+    fn tick_timers_counts_down_and_stops_at_zero() {
+        let mut chip8 = Chip8::new();
+        chip8.bus.set_delay_timer(2);
+        chip8.bus.set_sound_timer(1);
+
+        chip8.tick_timers();
+        assert_eq!(chip8.bus.delay_timer(), 1);
+        assert_eq!(chip8.bus.sound_timer(), 0);
+
+        chip8.tick_timers();
+        chip8.tick_timers();
+        assert_eq!(chip8.bus.delay_timer(), 0);
+        assert_eq!(chip8.bus.sound_timer(), 0);
+    }
+
+    #[test]
+    fn save_state_then_load_state_roundtrips_full_machine_state() {
+        let mut chip8 = Chip8::new();
+        // LD I, 0x300; CALL 0x206; CLS (unreached); CLS (the call target).
+        chip8.load(vec![0xA3, 0x00, 0x22, 0x06, 0x00, 0xE0, 0x00, 0xE0]);
+        chip8.set_key(0x3, true);
+        chip8.bus.set_delay_timer(10);
+        chip8.bus.set_sound_timer(5);
+        chip8.clock().unwrap();
+        chip8.clock().unwrap();
+
+        let blob = chip8.save_state();
+
+        let mut restored = Chip8::new();
+        restored.load_state(&blob).unwrap();
+
+        assert_eq!(restored.memory(), chip8.memory());
+        assert_eq!(restored.registers(), chip8.registers());
+        assert_eq!(restored.framebuffer(), chip8.framebuffer());
+        assert_eq!(restored.cpu.pc(), chip8.cpu.pc());
+        assert_eq!(restored.cpu.idx(), chip8.cpu.idx());
+        assert_eq!(restored.cpu.stack(), chip8.cpu.stack());
+        assert!(restored.bus.is_pressed(0x3));
+        assert_eq!(restored.bus.delay_timer(), 10);
+        assert_eq!(restored.bus.sound_timer(), 5);
     }
 
-    pub fn clock(&mut self) {
-        self.cpu.exec_instruction(&mut self.bus);
+    #[test]
+    fn load_state_rejects_a_blob_with_the_wrong_magic() {
+        let mut chip8 = Chip8::new();
+        assert!(chip8.load_state(&[0, 0, 0, 0, 1]).is_err());
     }
 }
@@ -1,4 +1,7 @@
 use crate::bus::Bus;
+use crate::error::ExecError;
+use crate::quirks::Quirks;
+use crate::ram::{FONT_CHAR_SIZE, FONT_START};
 use rand;
 use rand::Rng;
 
@@ -12,22 +15,28 @@ pub struct Cpu {
     idx: u16,
     stack: Vec<u16>,
     rng: rand::rngs::ThreadRng,
+    quirks: Quirks,
 }
 
 impl Cpu {
-    pub fn new() -> Self {
+    pub fn new(quirks: Quirks) -> Self {
         Self {
             vx: [0; NUM_REGISTERS],
             pc: PROGRAM_START,
             idx: 0,
             stack: Vec::with_capacity(STACK_CAPACITY),
             rng: rand::thread_rng(),
+            quirks,
         }
     }
 
-    pub fn exec_instruction(&mut self, bus: &mut Bus) {
-        let hi = bus.read_byte(self.pc) as u16;
-        let lo = bus.read_byte(self.pc + 1) as u16;
+    pub fn exec_instruction(&mut self, bus: &mut Bus) -> Result<(), ExecError> {
+        let hi = bus
+            .try_read_byte(self.pc)
+            .ok_or(ExecError::OutOfBoundsMemory(self.pc))? as u16;
+        let lo = bus
+            .try_read_byte(self.pc + 1)
+            .ok_or(ExecError::OutOfBoundsMemory(self.pc + 1))? as u16;
         let opcode = (hi << 8) | lo;
 
         // NNN : address, 12-bit value, the lowest 12 bits of the instruction
@@ -53,14 +62,14 @@ impl Cpu {
                 match nn {
                     0xE0 => {
                         // 00E0 Clears display
-                        // bus.clear_screen()
+                        bus.clear_screen();
                         self.pc += 2;
                     }
                     0xEE => {
                         // 00EE return from subroutine
-                        self.pc = self.stack.pop().unwrap();
+                        self.pc = self.stack.pop().ok_or(ExecError::StackUnderflow)?;
                     }
-                    _ => panic!("Unrecongnized 0x00** opcode {:X}:{:X}", self.pc, opcode),
+                    _ => return Err(ExecError::UnknownOpcode(opcode)),
                 }
             }
             0x1 => {
@@ -69,6 +78,9 @@ impl Cpu {
             }
             0x2 => {
                 // 2NNN Call subroutine at NNN
+                if self.stack.len() >= STACK_CAPACITY {
+                    return Err(ExecError::StackOverflow);
+                }
                 self.stack.push(self.pc + 2);
                 self.pc = nnn;
             }
@@ -136,33 +148,37 @@ impl Cpu {
                     }
                     0x5 => {
                         // 8XY5 Sub vy from value of vx. VF 0 if borrow and 1 if not
-                        self.load_reg(x, vx - vy);
+                        self.load_reg(x, vx.wrapping_sub(vy));
                         if vy > vx {
-                            self.load_reg(0xF, 1);
-                        } else {
                             self.load_reg(0xF, 0);
+                        } else {
+                            self.load_reg(0xF, 1);
                         }
                     }
                     0x6 => {
-                        // 8XY6 Stores least sig bit of vx in vf and then shift vx right 1
-                        self.load_reg(0xF, vx & 0x1);
-                        self.load_reg(x, vx >> 1);
+                        // 8XY6 Shift right by 1, storing the shifted-out bit in vf.
+                        // Shifts vy into vx when the quirk is enabled, else vx in place.
+                        let src = if self.quirks.shift_uses_vy { vy } else { vx };
+                        self.load_reg(0xF, src & 0x1);
+                        self.load_reg(x, src >> 1);
                     }
                     0x7 => {
                         // 8XY7 Set vx to vy - vx. vf 0 when borrow else 1
-                        self.load_reg(x, vy - vx);
+                        self.load_reg(x, vy.wrapping_sub(vx));
                         if vx > vy {
-                            self.load_reg(0xF, 1);
-                        } else {
                             self.load_reg(0xF, 0);
+                        } else {
+                            self.load_reg(0xF, 1);
                         }
                     }
                     0xE => {
-                        // 8XYE Stores most sig bit of vx in vf and then shift vx left 1
-                        self.load_reg(0xF, (vx >> 8) >> 7);
-                        self.load_reg(x, vx << 1);
+                        // 8XYE Shift left by 1, storing the shifted-out bit in vf.
+                        // Shifts vy into vx when the quirk is enabled, else vx in place.
+                        let src = if self.quirks.shift_uses_vy { vy } else { vx };
+                        self.load_reg(0xF, (src >> 7) & 0x1);
+                        self.load_reg(x, src << 1);
                     }
-                    _ => panic!("Unrecongnized 0x8XY* opcode {:X}:{:X}", self.pc, opcode),
+                    _ => return Err(ExecError::UnknownOpcode(opcode)),
                 }
 
                 self.pc += 2;
@@ -190,49 +206,115 @@ impl Cpu {
                 self.load_reg(x, r);
                 self.pc += 2;
             }
-            0xD => todo!(),
+            0xD => {
+                // DXYN Draw a sprite at (vx, vy) with width 8 and height n, reading
+                // n bytes of sprite data starting at I. VF is set if any set pixel
+                // is cleared (collision).
+                let sprite = bus
+                    .try_read_bytes(self.idx, n as u16)
+                    .ok_or(ExecError::OutOfBoundsMemory(self.idx))?
+                    .to_vec();
+                let collision = bus.draw_sprite(vx, vy, &sprite);
+                self.load_reg(0xF, collision as u8);
+                self.pc += 2;
+            }
             0xE => {
                 match nn {
-                    0x95 => {
-                        // EX9E Skip next instruction if key in vx not pressed
-                        todo!();
+                    0x9E => {
+                        // EX9E Skip next instruction if key in vx is pressed
+                        if bus.is_pressed(vx) {
+                            self.pc += 4;
+                        } else {
+                            self.pc += 2;
+                        }
                     }
                     0xA1 => {
-                        // EXA1 Skip next instruction if key in vx is pressed
-                        todo!();
+                        // EXA1 Skip next instruction if key in vx is not pressed
+                        if bus.is_pressed(vx) {
+                            self.pc += 2;
+                        } else {
+                            self.pc += 4;
+                        }
                     }
-                    _ => panic!("Unrecongnized 0xEX** opcode {:X}:{:X}", self.pc, opcode),
+                    _ => return Err(ExecError::UnknownOpcode(opcode)),
                 }
             }
             0xF => match nn {
                 0x07 => {
-                    todo!();
+                    // FX07 Set vx to the value of the delay timer
+                    self.load_reg(x, bus.delay_timer());
+                    self.pc += 2;
+                }
+                0x0A => {
+                    // FX0A Wait for a key press, store it in vx. Blocks by not
+                    // advancing PC until a key is down.
+                    if let Some(key) = bus.pressed_key() {
+                        self.load_reg(x, key);
+                        self.pc += 2;
+                    }
                 }
                 0x15 => {
-                    todo!();
+                    // FX15 Set the delay timer to vx
+                    bus.set_delay_timer(vx);
+                    self.pc += 2;
                 }
                 0x18 => {
-                    todo!();
+                    // FX18 Set the sound timer to vx
+                    bus.set_sound_timer(vx);
+                    self.pc += 2;
                 }
                 0x1E => {
-                    todo!();
+                    // FX1E Add vx to I
+                    self.idx = self.idx.wrapping_add(vx as u16);
+                    self.pc += 2;
                 }
                 0x29 => {
-                    todo!();
+                    // FX29 Set I to the address of the font sprite for the hex digit in vx
+                    self.idx = FONT_START + (vx as u16) * FONT_CHAR_SIZE;
+                    self.pc += 2;
                 }
                 0x33 => {
-                    todo!();
+                    // FX33 Store the BCD representation of vx at I, I+1, I+2
+                    bus.try_load_byte(self.idx, vx / 100)
+                        .ok_or(ExecError::OutOfBoundsMemory(self.idx))?;
+                    bus.try_load_byte(self.idx + 1, (vx / 10) % 10)
+                        .ok_or(ExecError::OutOfBoundsMemory(self.idx + 1))?;
+                    bus.try_load_byte(self.idx + 2, vx % 10)
+                        .ok_or(ExecError::OutOfBoundsMemory(self.idx + 2))?;
+                    self.pc += 2;
                 }
                 0x55 => {
-                    todo!();
+                    // FX55 Store v0..=vx into memory starting at I
+                    for offset in 0..=x {
+                        let address = self.idx + offset as u16;
+                        bus.try_load_byte(address, self.read_reg(offset))
+                            .ok_or(ExecError::OutOfBoundsMemory(address))?;
+                    }
+                    if self.quirks.load_store_increments_i {
+                        self.idx += x as u16 + 1;
+                    }
+                    self.pc += 2;
                 }
                 0x65 => {
-                    todo!();
+                    // FX65 Load v0..=vx from memory starting at I
+                    for offset in 0..=x {
+                        let address = self.idx + offset as u16;
+                        let value = bus
+                            .try_read_byte(address)
+                            .ok_or(ExecError::OutOfBoundsMemory(address))?;
+                        self.load_reg(offset, value);
+                    }
+                    if self.quirks.load_store_increments_i {
+                        self.idx += x as u16 + 1;
+                    }
+                    self.pc += 2;
                 }
-                _ => panic!("Unrecongnized 0xFX** opcode {:X}:{:X}", self.pc, opcode),
+                _ => return Err(ExecError::UnknownOpcode(opcode)),
             },
-            _ => panic!("Unrecongnized opcode {:X}:{:X}", self.pc, opcode),
+            _ => return Err(ExecError::UnknownOpcode(opcode)),
         }
+
+        Ok(())
     }
 
     pub fn read_reg(&self, index: u8) -> u8 {
@@ -242,4 +324,209 @@ impl Cpu {
     pub fn load_reg(&mut self, index: u8, value: u8) {
         self.vx[index as usize] = value;
     }
+
+    pub fn registers(&self) -> &[u8; NUM_REGISTERS] {
+        &self.vx
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    pub fn idx(&self) -> u16 {
+        self.idx
+    }
+
+    pub fn stack(&self) -> &[u16] {
+        &self.stack
+    }
+
+    pub fn restore(&mut self, vx: [u8; NUM_REGISTERS], pc: u16, idx: u16, stack: Vec<u16>) {
+        self.vx = vx;
+        self.pc = pc;
+        self.idx = idx;
+        self.stack = stack;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+
+    fn new_cpu() -> (Cpu, Bus) {
+        (Cpu::new(Quirks::default()), Bus::new())
+    }
+
+    fn load(bus: &mut Bus, address: u16, opcode: u16) {
+        bus.load_byte(address, (opcode >> 8) as u8);
+        bus.load_byte(address + 1, (opcode & 0xFF) as u8);
+    }
+
+    #[test]
+    fn fx29_points_i_at_the_requested_digit_glyph() {
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0xF229); // LD F, V2
+        cpu.load_reg(2, 0x3);
+
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.idx(), FONT_START + 3 * FONT_CHAR_SIZE);
+    }
+
+    #[test]
+    fn fx0a_blocks_until_a_key_is_pressed() {
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0xF00A); // LD V0, K
+
+        cpu.exec_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.pc(), PROGRAM_START); // no key pressed yet, pc does not advance
+
+        bus.set_key(0x7, true);
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.pc(), PROGRAM_START + 2);
+        assert_eq!(cpu.read_reg(0), 0x7);
+    }
+
+    #[test]
+    fn ex9e_does_not_panic_on_an_out_of_range_vx() {
+        // LD V0, 0xFF; SKP V0
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0x60FF);
+        load(&mut bus, PROGRAM_START + 2, 0xE09E);
+
+        cpu.exec_instruction(&mut bus).unwrap();
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.pc(), PROGRAM_START + 4);
+    }
+
+    #[test]
+    fn fx33_stores_the_bcd_digits_of_vx() {
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0xF033); // LD B, V0
+        cpu.load_reg(0, 123);
+        cpu.idx = 0x300;
+
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(bus.read_byte(0x300), 1);
+        assert_eq!(bus.read_byte(0x301), 2);
+        assert_eq!(bus.read_byte(0x302), 3);
+    }
+
+    #[test]
+    fn fx55_then_fx65_roundtrips_with_increment_quirk_enabled() {
+        let mut cpu = Cpu::new(Quirks {
+            load_store_increments_i: true,
+            shift_uses_vy: false,
+        });
+        let mut bus = Bus::new();
+        for i in 0..=2u8 {
+            cpu.load_reg(i, i + 1);
+        }
+        cpu.idx = 0x300;
+        load(&mut bus, PROGRAM_START, 0xF255); // LD [I], V2
+        cpu.exec_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.idx(), 0x303);
+
+        let mut cpu2 = Cpu::new(Quirks {
+            load_store_increments_i: true,
+            shift_uses_vy: false,
+        });
+        cpu2.idx = 0x300;
+        load(&mut bus, PROGRAM_START + 2, 0xF265); // LD V2, [I]
+        cpu2.pc = PROGRAM_START + 2;
+        cpu2.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu2.read_reg(0), 1);
+        assert_eq!(cpu2.read_reg(1), 2);
+        assert_eq!(cpu2.read_reg(2), 3);
+        assert_eq!(cpu2.idx(), 0x303);
+    }
+
+    #[test]
+    fn fx55_leaves_i_unchanged_when_increment_quirk_is_disabled() {
+        let mut cpu = Cpu::new(Quirks {
+            load_store_increments_i: false,
+            shift_uses_vy: false,
+        });
+        let mut bus = Bus::new();
+        cpu.load_reg(0, 0x42);
+        cpu.idx = 0x300;
+        load(&mut bus, PROGRAM_START, 0xF055); // LD [I], V0
+
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.idx(), 0x300);
+        assert_eq!(bus.read_byte(0x300), 0x42);
+    }
+
+    #[test]
+    fn shr_shifts_vy_into_vx_when_quirk_enabled() {
+        let mut cpu = Cpu::new(Quirks {
+            load_store_increments_i: false,
+            shift_uses_vy: true,
+        });
+        let mut bus = Bus::new();
+        cpu.load_reg(2, 0b0000_0011);
+        load(&mut bus, PROGRAM_START, 0x8126); // SHR V1, V2
+
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.read_reg(1), 0b0000_0001);
+        assert_eq!(cpu.read_reg(0xF), 1);
+    }
+
+    #[test]
+    fn shr_shifts_vx_in_place_when_quirk_disabled() {
+        let mut cpu = Cpu::new(Quirks {
+            load_store_increments_i: false,
+            shift_uses_vy: false,
+        });
+        let mut bus = Bus::new();
+        cpu.load_reg(1, 0b0000_0011);
+        cpu.load_reg(2, 0xFF);
+        load(&mut bus, PROGRAM_START, 0x8126); // SHR V1, V2
+
+        cpu.exec_instruction(&mut bus).unwrap();
+
+        assert_eq!(cpu.read_reg(1), 0b0000_0001);
+        assert_eq!(cpu.read_reg(0xF), 1);
+    }
+
+    #[test]
+    fn unknown_opcode_returns_an_error_instead_of_panicking() {
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0xFFFF);
+
+        assert_eq!(
+            cpu.exec_instruction(&mut bus),
+            Err(ExecError::UnknownOpcode(0xFFFF))
+        );
+    }
+
+    #[test]
+    fn ret_with_an_empty_stack_is_a_stack_underflow() {
+        let (mut cpu, mut bus) = new_cpu();
+        load(&mut bus, PROGRAM_START, 0x00EE); // RET
+
+        assert_eq!(cpu.exec_instruction(&mut bus), Err(ExecError::StackUnderflow));
+    }
+
+    #[test]
+    fn call_beyond_stack_capacity_is_a_stack_overflow() {
+        let (mut cpu, mut bus) = new_cpu();
+        // Fill the call stack with self-calls to 0x200, then try one more.
+        for address in (PROGRAM_START..PROGRAM_START + STACK_CAPACITY as u16 * 2).step_by(2) {
+            load(&mut bus, address, 0x2000 | PROGRAM_START);
+        }
+
+        for _ in 0..STACK_CAPACITY {
+            cpu.exec_instruction(&mut bus).unwrap();
+        }
+
+        assert_eq!(cpu.exec_instruction(&mut bus), Err(ExecError::StackOverflow));
+    }
 }
@@ -0,0 +1,118 @@
+pub const DISPLAY_WIDTH: usize = 64;
+pub const DISPLAY_HEIGHT: usize = 32;
+
+pub struct Display {
+    pixels: [bool; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+}
+
+impl Display {
+    pub fn new() -> Self {
+        Self {
+            pixels: [false; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pixels = [false; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+    }
+
+    pub fn pixels(&self) -> &[bool] {
+        &self.pixels
+    }
+
+    pub fn restore(&mut self, pixels: &[bool]) {
+        self.pixels.copy_from_slice(pixels);
+    }
+
+    /// Draws a sprite made up of `sprite` rows at `(x, y)`, XORing each bit onto the
+    /// framebuffer. The starting position wraps around the screen, but pixels that
+    /// would fall off the edge while drawing are clipped rather than wrapped.
+    /// Returns true if any previously set pixel was cleared (a collision).
+    pub fn draw_sprite(&mut self, x: u8, y: u8, sprite: &[u8]) -> bool {
+        let start_x = x as usize % DISPLAY_WIDTH;
+        let start_y = y as usize % DISPLAY_HEIGHT;
+        let mut collision = false;
+
+        for (row, byte) in sprite.iter().enumerate() {
+            let py = start_y + row;
+            if py >= DISPLAY_HEIGHT {
+                break;
+            }
+
+            for bit in 0..8 {
+                let px = start_x + bit;
+                if px >= DISPLAY_WIDTH {
+                    continue;
+                }
+
+                let pixel = (byte >> (7 - bit)) & 0x1 == 1;
+                if pixel {
+                    let idx = py * DISPLAY_WIDTH + px;
+                    if self.pixels[idx] {
+                        collision = true;
+                    }
+                    self.pixels[idx] ^= true;
+                }
+            }
+        }
+
+        collision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_sprite_sets_pixels_with_no_collision_on_first_draw() {
+        let mut display = Display::new();
+        let collision = display.draw_sprite(0, 0, &[0xF0]);
+
+        assert!(!collision);
+        assert!(display.pixels()[0]);
+        assert!(display.pixels()[1]);
+        assert!(display.pixels()[2]);
+        assert!(display.pixels()[3]);
+        assert!(!display.pixels()[4]);
+    }
+
+    #[test]
+    fn draw_sprite_twice_xors_and_reports_collision() {
+        let mut display = Display::new();
+        display.draw_sprite(0, 0, &[0xF0]);
+        let collision = display.draw_sprite(0, 0, &[0xF0]);
+
+        assert!(collision);
+        assert!(display.pixels().iter().all(|&p| !p));
+    }
+
+    #[test]
+    fn draw_sprite_clips_instead_of_wrapping_at_the_right_edge() {
+        let mut display = Display::new();
+        display.draw_sprite((DISPLAY_WIDTH - 4) as u8, 0, &[0xFF]);
+
+        assert!(display.pixels()[DISPLAY_WIDTH - 4]);
+        assert!(display.pixels()[DISPLAY_WIDTH - 1]);
+        // The sprite's low nibble would land at column 0 if it wrapped; it must not.
+        assert!(!display.pixels()[0]);
+    }
+
+    #[test]
+    fn draw_sprite_wraps_the_starting_position() {
+        let mut display = Display::new();
+        display.draw_sprite((DISPLAY_WIDTH + 2) as u8, (DISPLAY_HEIGHT + 1) as u8, &[0x80]);
+
+        let idx = DISPLAY_WIDTH + 2;
+        assert!(display.pixels()[idx]);
+    }
+
+    #[test]
+    fn clear_resets_all_pixels() {
+        let mut display = Display::new();
+        display.draw_sprite(0, 0, &[0xFF]);
+        display.clear();
+
+        assert!(display.pixels().iter().all(|&p| !p));
+    }
+}
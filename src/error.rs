@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Errors that can occur while decoding or executing a single instruction.
+/// `Cpu::exec_instruction` returns these instead of panicking so malformed or
+/// fuzzed ROMs can be rejected cleanly rather than crashing the interpreter.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecError {
+    UnknownOpcode(u16),
+    StackUnderflow,
+    StackOverflow,
+    OutOfBoundsMemory(u16),
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecError::UnknownOpcode(opcode) => write!(f, "unrecognized opcode {:04X}", opcode),
+            ExecError::StackUnderflow => write!(f, "stack underflow on return"),
+            ExecError::StackOverflow => write!(f, "call stack overflow"),
+            ExecError::OutOfBoundsMemory(address) => {
+                write!(f, "out-of-bounds memory access at {:04X}", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
@@ -0,0 +1,66 @@
+use crate::chip8::Chip8;
+use crate::error::ExecError;
+
+/// Runs `chip8` for up to `max_cycles` instructions, returning the error that
+/// stopped it (if any). Intended for tests that load a ROM and then assert on
+/// register/memory/framebuffer state after a bounded number of steps.
+pub fn run_bounded(chip8: &mut Chip8, max_cycles: usize) -> Result<(), ExecError> {
+    for _ in 0..max_cycles {
+        chip8.clock()?;
+    }
+    Ok(())
+}
+
+/// Fuzzing entry point: loads an arbitrary byte buffer as a ROM and steps the
+/// interpreter, relying on `exec_instruction` returning `Result` instead of
+/// panicking on malformed opcodes. Safe to call with any input.
+pub fn fuzz_target(data: &[u8]) {
+    const MAX_CYCLES: usize = 10_000;
+
+    let mut chip8 = Chip8::new();
+    chip8.load(data.to_vec());
+
+    for _ in 0..MAX_CYCLES {
+        if chip8.clock().is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ExecError;
+
+    #[test]
+    fn run_bounded_stops_with_an_error_on_an_unknown_opcode() {
+        let mut chip8 = Chip8::new();
+        chip8.load(vec![0xFF, 0xFF]);
+
+        assert_eq!(run_bounded(&mut chip8, 10), Err(ExecError::UnknownOpcode(0xFFFF)));
+    }
+
+    #[test]
+    fn run_bounded_stops_cleanly_when_max_cycles_is_reached() {
+        let mut chip8 = Chip8::new();
+        chip8.load(vec![0x12, 0x00]); // JP 0x200, an infinite loop
+
+        assert_eq!(run_bounded(&mut chip8, 1_000), Ok(()));
+    }
+
+    #[test]
+    fn fuzz_target_never_panics_on_known_tricky_roms() {
+        let roms: &[&[u8]] = &[
+            &[0x00, 0xEE],                   // bare RET with an empty stack
+            &[0x22, 0x00],                   // 2NNN self-call chain, triggers stack overflow
+            &[0x60, 0xFF, 0xE0, 0x9E],       // LD V0, 0xFF; SKP V0 (out-of-range key repro)
+            &[0xFF; 64],                     // garbage opcodes
+            &[0x00],                         // truncated, odd-length buffer
+            &[],                             // empty buffer
+        ];
+
+        for rom in roms {
+            fuzz_target(rom);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+pub const NUM_KEYS: usize = 16;
+
+pub struct Keypad {
+    keys: [bool; NUM_KEYS],
+}
+
+impl Keypad {
+    pub fn new() -> Self {
+        Self {
+            keys: [false; NUM_KEYS],
+        }
+    }
+
+    /// `key` is a CHIP-8 key id, conventionally 0-F. Out-of-range values are
+    /// wrapped into that space instead of indexing out of bounds, since
+    /// callers may pass a raw `VX` register value straight from a ROM.
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.keys[key as usize % NUM_KEYS] = pressed;
+    }
+
+    pub fn is_pressed(&self, key: u8) -> bool {
+        self.keys[key as usize % NUM_KEYS]
+    }
+
+    pub fn pressed_key(&self) -> Option<u8> {
+        self.keys.iter().position(|&k| k).map(|k| k as u8)
+    }
+
+    pub fn keys(&self) -> &[bool; NUM_KEYS] {
+        &self.keys
+    }
+
+    pub fn restore(&mut self, keys: [bool; NUM_KEYS]) {
+        self.keys = keys;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_key_then_is_pressed_roundtrips() {
+        let mut keypad = Keypad::new();
+        assert!(!keypad.is_pressed(0xA));
+
+        keypad.set_key(0xA, true);
+        assert!(keypad.is_pressed(0xA));
+
+        keypad.set_key(0xA, false);
+        assert!(!keypad.is_pressed(0xA));
+    }
+
+    #[test]
+    fn pressed_key_returns_the_lowest_index_held_down() {
+        let mut keypad = Keypad::new();
+        keypad.set_key(0x5, true);
+        keypad.set_key(0x2, true);
+
+        assert_eq!(keypad.pressed_key(), Some(0x2));
+    }
+
+    #[test]
+    fn pressed_key_is_none_when_nothing_is_held() {
+        let keypad = Keypad::new();
+        assert_eq!(keypad.pressed_key(), None);
+    }
+
+    #[test]
+    fn out_of_range_key_wraps_instead_of_panicking() {
+        let mut keypad = Keypad::new();
+        keypad.set_key(0xFF, true);
+
+        // 0xFF % NUM_KEYS == 0xF
+        assert!(keypad.is_pressed(0xF));
+        assert!(keypad.is_pressed(0xFF));
+    }
+}
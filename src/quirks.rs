@@ -0,0 +1,20 @@
+/// CHIP-8 interpreters disagree on a handful of opcode behaviours. `Quirks`
+/// lets a front-end pick which interpretation a given ROM expects instead of
+/// hard-coding one.
+pub struct Quirks {
+    /// Whether `FX55`/`FX65` leave `I` as `I + X + 1` afterwards (the original
+    /// COSMAC VIP behaviour) or leave `I` unchanged (as later interpreters do).
+    pub load_store_increments_i: bool,
+    /// Whether `8XY6`/`8XYE` shift `vy` into `vx` (the original behaviour) or
+    /// shift `vx` in place, ignoring `vy`.
+    pub shift_uses_vy: bool,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self {
+            load_store_increments_i: true,
+            shift_uses_vy: false,
+        }
+    }
+}
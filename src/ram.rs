@@ -1,4 +1,30 @@
-const MEMORY_SIZE: usize = 1 * 1024;
+pub const MEMORY_SIZE: usize = 4 * 1024;
+
+/// Address of the built-in hex digit font, conventionally placed at the start
+/// of the reserved interpreter area (before `PROGRAM_START`).
+pub const FONT_START: u16 = 0x000;
+/// Each hex digit sprite is 5 bytes tall.
+pub const FONT_CHAR_SIZE: u16 = 5;
+
+/// The standard CHIP-8 hex digit font (0-F), 5 bytes per glyph.
+const FONT: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x20, 0x60, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
+];
 
 pub struct Ram {
     mem: [u8; MEMORY_SIZE],
@@ -6,16 +32,85 @@ pub struct Ram {
 
 impl Ram {
     pub fn new() -> Self {
-        Self {
-            mem: [0; MEMORY_SIZE],
-        }
+        let mut mem = [0; MEMORY_SIZE];
+        let font_end = FONT_START as usize + FONT.len();
+        mem[FONT_START as usize..font_end].copy_from_slice(&FONT);
+
+        Self { mem }
     }
 
     pub fn read_byte(&self, address: u16) -> u8 {
         self.mem[address as usize]
     }
 
+    pub fn read_bytes(&self, address: u16, len: u16) -> &[u8] {
+        let start = address as usize;
+        let end = start + len as usize;
+        &self.mem[start..end]
+    }
+
     pub fn load_byte(&mut self, address: u16, value: u8) {
         self.mem[address as usize] = value;
     }
+
+    pub fn try_read_byte(&self, address: u16) -> Option<u8> {
+        self.mem.get(address as usize).copied()
+    }
+
+    pub fn try_read_bytes(&self, address: u16, len: u16) -> Option<&[u8]> {
+        let start = address as usize;
+        let end = start + len as usize;
+        self.mem.get(start..end)
+    }
+
+    pub fn try_load_byte(&mut self, address: u16, value: u8) -> Option<()> {
+        let slot = self.mem.get_mut(address as usize)?;
+        *slot = value;
+        Some(())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mem
+    }
+
+    pub fn load_bytes(&mut self, bytes: &[u8]) {
+        self.mem.copy_from_slice(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_preloads_the_font_at_font_start() {
+        let ram = Ram::new();
+        // Digit 0's glyph, the first entry in the standard font table.
+        assert_eq!(ram.read_bytes(FONT_START, FONT_CHAR_SIZE), &[0xF0, 0x90, 0x90, 0x90, 0xF0]);
+    }
+
+    #[test]
+    fn load_byte_then_read_byte_roundtrips() {
+        let mut ram = Ram::new();
+        ram.load_byte(0x200, 0xAB);
+        assert_eq!(ram.read_byte(0x200), 0xAB);
+    }
+
+    #[test]
+    fn try_read_byte_out_of_bounds_returns_none() {
+        let ram = Ram::new();
+        assert_eq!(ram.try_read_byte(MEMORY_SIZE as u16), None);
+    }
+
+    #[test]
+    fn try_load_byte_out_of_bounds_returns_none_without_panicking() {
+        let mut ram = Ram::new();
+        assert_eq!(ram.try_load_byte(u16::MAX, 0x42), None);
+    }
+
+    #[test]
+    fn try_read_bytes_out_of_bounds_returns_none() {
+        let ram = Ram::new();
+        assert_eq!(ram.try_read_bytes((MEMORY_SIZE - 1) as u16, 2), None);
+    }
 }